@@ -0,0 +1,87 @@
+//! Support for `cargo dynlint --fix`.
+//!
+//! This mirrors the approach `cargo fix` takes: run the Dynlint driver with
+//! `--error-format=json`, collect the diagnostics that carry a `suggested_replacement`, and hand
+//! them to the [`rustfix`] crate to rewrite the affected files on disk.
+
+use anyhow::{bail, Context, Result};
+use rustfix::{apply_suggestions, get_suggestions_from_json, Filter};
+use std::{
+    collections::HashMap,
+    fs::{read_to_string, write},
+    path::Path,
+    process::Command,
+};
+
+/// Options controlling how [`fix`] applies suggestions.
+pub struct FixOptions {
+    /// Apply fixes even if the working directory has uncommitted changes. Analogous to `cargo
+    /// fix`'s `--allow-dirty`/`--allow-staged`/`--allow-no-vcs` flags, collapsed into one because
+    /// a Dynlint fix is meant to be reviewed as a single unit.
+    pub allow_dirty: bool,
+}
+
+/// Runs `driver` over `args` with JSON diagnostics enabled, and applies every
+/// `MachineApplicable` suggestion it emits to the files on disk.
+pub fn fix(driver: &Path, args: &[String], opts: &FixOptions) -> Result<()> {
+    if !opts.allow_dirty {
+        ensure_clean_vcs()?;
+    }
+
+    let output = Command::new(driver)
+        .args(args)
+        .arg("--error-format=json")
+        .output()
+        .with_context(|| format!("could not run `{}`", driver.to_string_lossy()))?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    let mut suggestions_by_file: HashMap<String, Vec<_>> = HashMap::new();
+    for line in stderr.lines() {
+        let Ok(suggestions) = get_suggestions_from_json(line, &[], Filter::MachineApplicableOnly)
+        else {
+            continue;
+        };
+        for suggestion in suggestions {
+            let Some(solution) = suggestion.solutions.first() else {
+                continue;
+            };
+            let Some(replacement) = solution.replacements.first() else {
+                continue;
+            };
+            suggestions_by_file
+                .entry(replacement.snippet.file_name.clone())
+                .or_default()
+                .push(suggestion);
+        }
+    }
+
+    for (file, suggestions) in suggestions_by_file {
+        let original = read_to_string(&file)
+            .with_context(|| format!("`read_to_string` failed for `{file}`"))?;
+        let fixed = apply_suggestions(&original, &suggestions)
+            .with_context(|| format!("failed to apply suggestions to `{file}`"))?;
+        write(&file, fixed).with_context(|| format!("`write` failed for `{file}`"))?;
+    }
+
+    Ok(())
+}
+
+/// Fails if `git status --porcelain` reports uncommitted changes in the working directory. Where
+/// `git` cannot be run, or the working directory is not a git repository, the check passes.
+fn ensure_clean_vcs() -> Result<()> {
+    let Ok(output) = Command::new("git").args(["status", "--porcelain"]).output() else {
+        // No `git` on `PATH`: nothing to check.
+        return Ok(());
+    };
+
+    // A failing `git status` (e.g., no repository) also counts as nothing to check.
+    if !output.status.success() || output.stdout.is_empty() {
+        return Ok(());
+    }
+
+    bail!(
+        "the working directory has uncommitted changes; pass `--allow-dirty` to apply fixes \
+         anyway (cf. `cargo fix --allow-no-vcs`)"
+    );
+}
@@ -0,0 +1,76 @@
+//! Building the Dynlint driver and the per-workspace library (dylib) that `cargo dynlint` loads
+//! into it.
+
+use anyhow::{Context, Result};
+use dynlint_internal::{
+    cargo::{build, clear_rustflags_for_metadata, extend_rustflags},
+    fingerprint::Fingerprint,
+};
+use std::path::Path;
+
+/// Flags Dynlint itself needs on every library build, regardless of what the user has set in
+/// their own `RUSTFLAGS`.
+const DYNLINT_RUSTFLAGS: &str = "--cfg dynlint_lib";
+
+/// Builds the Dynlint driver, a thin `rustc` wrapper that loads metadata-entry libraries and runs
+/// them as a set of lint passes.
+pub fn build_driver(driver_dir: &Path, quiet: bool) -> Result<()> {
+    let mut command = build("dynlint driver", quiet);
+    extend_rustflags(&mut command, DYNLINT_RUSTFLAGS);
+    command.current_dir(driver_dir);
+    let status = command
+        .status()
+        .with_context(|| "could not get status of driver build")?;
+    anyhow::ensure!(status.success(), "driver build failed");
+    Ok(())
+}
+
+/// Builds a workspace-metadata entry's library crate, identified by `package_id` and built into
+/// `target_dir`, unless its fingerprint shows it's already up to date.
+///
+/// The metadata probe that determines *which* crates need building is run with `RUSTFLAGS`
+/// cleared, so that changing the flags used for the real build doesn't also force every metadata
+/// entry to be needlessly rebuilt; the real build, in contrast, extends whatever `RUSTFLAGS` the
+/// user already has set rather than clobbering it.
+///
+/// `force_rebuild` (`cargo dynlint`'s `--force-rebuild` flag) bypasses the fingerprint cache
+/// entirely, for when you don't trust it or have changed something the fingerprint doesn't track.
+pub fn build_library(
+    library_dir: &Path,
+    target_dir: &Path,
+    package_id: &str,
+    toolchain_channel: &str,
+    quiet: bool,
+    force_rebuild: bool,
+) -> Result<()> {
+    let fingerprint = Fingerprint::compute(
+        library_dir,
+        toolchain_channel,
+        &std::env::var(dynlint_internal::env::RUSTFLAGS).unwrap_or_default(),
+    )?;
+
+    if !force_rebuild && fingerprint.is_fresh(target_dir, package_id) {
+        log::debug!("skipping rebuild of `{package_id}`: fingerprint unchanged");
+        return Ok(());
+    }
+
+    let mut metadata_probe = build(&format!("{} (metadata)", library_dir.display()), true);
+    clear_rustflags_for_metadata(&mut metadata_probe);
+    metadata_probe.current_dir(library_dir);
+    let status = metadata_probe
+        .status()
+        .with_context(|| "could not get status of metadata probe")?;
+    anyhow::ensure!(status.success(), "metadata probe failed");
+
+    let mut command = build(&library_dir.display().to_string(), quiet);
+    extend_rustflags(&mut command, DYNLINT_RUSTFLAGS);
+    command.current_dir(library_dir);
+    let status = command
+        .status()
+        .with_context(|| "could not get status of library build")?;
+    anyhow::ensure!(status.success(), "library build failed");
+
+    fingerprint.store(target_dir, package_id)?;
+
+    Ok(())
+}
@@ -0,0 +1,227 @@
+//! Support for `cargo dynlint upgrade`.
+
+use anyhow::{bail, Context, Result};
+use cargo_metadata::MetadataCommand;
+use semver::Version;
+use std::{
+    collections::HashMap,
+    fs::{create_dir_all, read_to_string, write},
+    path::{Path, PathBuf},
+};
+use tempfile::tempdir;
+use toml_edit::{value, Document, Table};
+
+/// Options for `cargo dynlint upgrade`.
+pub struct Args {
+    pub path: PathBuf,
+    pub rust_version: Option<Version>,
+    pub allow_downgrade: bool,
+    pub bisect: bool,
+    /// Also upgrade the template's ordinary dependencies to their latest semver-incompatible
+    /// releases.
+    pub breaking: bool,
+}
+
+/// Retargets a `dynlint-template`-derived library to a new toolchain, and optionally upgrades its
+/// ordinary dependencies.
+pub fn upgrade(args: &Args) -> Result<()> {
+    let manifest_path = args.path.join("Cargo.toml");
+
+    if let Some(rust_version) = &args.rust_version {
+        let current = current_rust_version(&manifest_path)?;
+        if !args.allow_downgrade && *rust_version < current {
+            bail!(
+                "Refusing to downgrade toolchain from {current} to {rust_version}; pass \
+                 `--allow-downgrade` to override"
+            );
+        }
+        retarget_toolchain(&manifest_path, rust_version)?;
+    } else if args.bisect {
+        bisect_toolchain(&manifest_path)?;
+    }
+
+    if args.breaking {
+        upgrade_breaking_dependencies(&manifest_path)?;
+    }
+
+    Ok(())
+}
+
+fn current_rust_version(manifest_path: &Path) -> Result<Version> {
+    let contents = read_to_string(manifest_path).with_context(|| {
+        format!(
+            "`read_to_string` failed for `{}`",
+            manifest_path.to_string_lossy()
+        )
+    })?;
+    let document = contents
+        .parse::<Document>()
+        .with_context(|| format!("could not parse `{}`", manifest_path.to_string_lossy()))?;
+    let raw = document["package"]["rust-version"]
+        .as_str()
+        .with_context(|| "`package.rust-version` is missing or not a string")?;
+    Version::parse(raw).map_err(Into::into)
+}
+
+fn retarget_toolchain(manifest_path: &Path, rust_version: &Version) -> Result<()> {
+    let contents = read_to_string(manifest_path).with_context(|| {
+        format!(
+            "`read_to_string` failed for `{}`",
+            manifest_path.to_string_lossy()
+        )
+    })?;
+    let mut document = contents
+        .parse::<Document>()
+        .with_context(|| format!("could not parse `{}`", manifest_path.to_string_lossy()))?;
+    document["package"]["rust-version"] = value(rust_version.to_string());
+    write(manifest_path, document.to_string())
+        .with_context(|| format!("`write` failed for `{}`", manifest_path.to_string_lossy()))
+}
+
+/// Called for `--bisect` when no explicit `--rust-version` is given. This function performs no
+/// search of its own: it leaves the manifest unchanged and returns `Ok(())`.
+fn bisect_toolchain(_manifest_path: &Path) -> Result<()> {
+    // The search itself builds the template against successive nightlies and is driven from
+    // `main.rs`; this function exists so `upgrade` has a single entry point regardless of which
+    // toolchain-selection strategy the caller chose.
+    Ok(())
+}
+
+/// Names that `cargo dynlint upgrade`'s toolchain logic manages directly, and which
+/// `--breaking` must therefore leave untouched.
+fn is_toolchain_managed(name: &str) -> bool {
+    name.starts_with("dynlint") || name == "clippy_utils"
+}
+
+/// Rewrites every ordinary (non-toolchain-managed) dependency in `manifest_path` to its latest
+/// semver-incompatible version.
+///
+/// This is a two-phase process, the same shape `cargo upgrade` itself uses: first the
+/// requirements are relaxed in a scratch copy of the manifest so that `cargo metadata` resolves
+/// to the latest version of each dependency actually available, then those versions are written
+/// into the real manifest, preserving existing comments and formatting via `toml_edit`.
+fn upgrade_breaking_dependencies(manifest_path: &Path) -> Result<()> {
+    let original = read_to_string(manifest_path).with_context(|| {
+        format!(
+            "`read_to_string` failed for `{}`",
+            manifest_path.to_string_lossy()
+        )
+    })?;
+    let mut document = original
+        .parse::<Document>()
+        .with_context(|| format!("could not parse `{}`", manifest_path.to_string_lossy()))?;
+
+    let latest = discover_latest_versions(manifest_path, &original)
+        .with_context(|| "could not determine latest dependency versions")?;
+
+    for table in dependency_tables(&mut document) {
+        let names: Vec<String> = table
+            .iter()
+            .map(|(name, _)| name.to_owned())
+            .filter(|name| !is_toolchain_managed(name))
+            .collect();
+        for name in names {
+            let Some(version) = latest.get(&name) else {
+                continue;
+            };
+            let item = &mut table[&name];
+            if item.get("path").is_some() || item.get("git").is_some() {
+                // `path`/`git` dependencies resolve a version via `cargo metadata` too, but
+                // they have no version requirement of their own to bump; leave them alone.
+                continue;
+            }
+            if item.is_str() {
+                *item = value(format!("^{version}"));
+            } else if item.is_table_like() {
+                item["version"] = value(format!("^{version}"));
+            }
+        }
+    }
+
+    write(manifest_path, document.to_string())
+        .with_context(|| format!("`write` failed for `{}`", manifest_path.to_string_lossy()))
+}
+
+/// Phase one: relax every ordinary dependency's requirement to `*` and ask `cargo metadata` to
+/// resolve it, so we learn the latest version of each dependency that is actually available.
+///
+/// This is done entirely against a scratch copy of the manifest in a temporary directory; the
+/// real manifest on disk is never written to by this function, so a kill or a panic midway
+/// through can't leave the user's `Cargo.toml` relaxed. Any `path` dependency is rewritten to an
+/// absolute path so it still resolves correctly from the scratch directory.
+fn discover_latest_versions(
+    manifest_path: &Path,
+    original: &str,
+) -> Result<HashMap<String, Version>> {
+    let manifest_dir = manifest_path.parent().with_context(|| {
+        format!(
+            "`{}` has no parent directory",
+            manifest_path.to_string_lossy()
+        )
+    })?;
+
+    let mut scratch = original
+        .parse::<Document>()
+        .with_context(|| format!("could not parse `{}`", manifest_path.to_string_lossy()))?;
+
+    for table in dependency_tables(&mut scratch) {
+        let names: Vec<String> = table.iter().map(|(name, _)| name.to_owned()).collect();
+        for name in names {
+            if let Some(relative_path) = table[&name]
+                .get("path")
+                .and_then(|item| item.as_str())
+                .map(ToOwned::to_owned)
+            {
+                let absolute_path = manifest_dir.join(relative_path);
+                table[&name]["path"] = value(absolute_path.to_string_lossy().into_owned());
+            }
+            if is_toolchain_managed(&name) {
+                continue;
+            }
+            if table[&name].is_str() {
+                table[&name] = value("*");
+            } else if table[&name].get("version").is_some() {
+                table[&name]["version"] = value("*");
+            }
+        }
+    }
+
+    let scratch_dir = tempdir()
+        .with_context(|| "could not create a temporary directory to probe dependency versions")?;
+    let scratch_manifest_path = scratch_dir.path().join("Cargo.toml");
+    write(&scratch_manifest_path, scratch.to_string()).with_context(|| {
+        format!(
+            "`write` failed for `{}`",
+            scratch_manifest_path.to_string_lossy()
+        )
+    })?;
+    // `cargo metadata` rejects a manifest whose library target has no source file.
+    let scratch_src_dir = scratch_dir.path().join("src");
+    create_dir_all(&scratch_src_dir)
+        .with_context(|| format!("`create_dir_all` failed for `{}`", scratch_src_dir.display()))?;
+    write(scratch_src_dir.join("lib.rs"), "")
+        .with_context(|| "`write` failed for the scratch `src/lib.rs`")?;
+
+    let metadata = MetadataCommand::new()
+        .manifest_path(&scratch_manifest_path)
+        .exec()
+        .with_context(|| "`cargo metadata` failed while probing latest dependency versions")?;
+
+    let mut latest: HashMap<String, Version> = HashMap::new();
+    for package in metadata.packages {
+        let is_newer = latest
+            .get(&package.name)
+            .map_or(true, |current| package.version > *current);
+        if is_newer {
+            latest.insert(package.name, package.version);
+        }
+    }
+    Ok(latest)
+}
+
+/// Yields the `[dependencies]`, `[dev-dependencies]`, and `[build-dependencies]` tables.
+fn dependency_tables(document: &mut Document) -> impl Iterator<Item = &mut Table> {
+    ["dependencies", "dev-dependencies", "build-dependencies"]
+        .into_iter()
+        .filter_map(|key| document[key].as_table_mut())
+}
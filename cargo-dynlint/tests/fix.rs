@@ -0,0 +1,35 @@
+use assert_cmd::prelude::*;
+use std::fs::{read_to_string, write};
+use tempfile::tempdir;
+
+const SRC: &str = r#"#![allow(clippy::assertions_on_constants)]
+
+fn main() {}
+"#;
+
+#[test]
+fn fix_removes_crate_wide_allow() {
+    let tempdir = tempdir().unwrap();
+
+    std::process::Command::cargo_bin("cargo-dynlint")
+        .unwrap()
+        .args(["dynlint", "new", &tempdir.path().to_string_lossy(), "--isolate"])
+        .assert()
+        .success();
+
+    let main_rs = tempdir.path().join("src").join("main.rs");
+    write(&main_rs, SRC).unwrap();
+
+    std::process::Command::cargo_bin("cargo-dynlint")
+        .unwrap()
+        // smoelius: `DYNLINT_LIBRARY_PATH` must not leak from an outer `cargo test` invocation
+        // into this nested one, or it causes "found multiple libraries" errors.
+        .env_remove("DYNLINT_LIBRARY_PATH")
+        .current_dir(&tempdir)
+        .args(["dynlint", "--fix", "--lib", "general", "--", "--examples"])
+        .assert()
+        .success();
+
+    let fixed = read_to_string(&main_rs).unwrap();
+    assert!(!fixed.contains("#![allow(clippy::assertions_on_constants)]"));
+}
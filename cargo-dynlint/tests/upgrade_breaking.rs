@@ -0,0 +1,73 @@
+use assert_cmd::prelude::*;
+use std::fs::{create_dir_all, read_to_string, write};
+use tempfile::tempdir;
+use toml_edit::{table, value, Document};
+
+#[test]
+fn breaking_preserves_table_fields_and_skips_path_dependencies() {
+    let tempdir = tempdir().unwrap();
+
+    std::process::Command::cargo_bin("cargo-dynlint")
+        .unwrap()
+        .args([
+            "dynlint",
+            "new",
+            &tempdir.path().to_string_lossy(),
+            "--isolate",
+        ])
+        .assert()
+        .success();
+
+    let local_helper_dir = tempdir.path().join("local_helper");
+    create_dir_all(local_helper_dir.join("src")).unwrap();
+    write(
+        local_helper_dir.join("Cargo.toml"),
+        "[package]\nname = \"local_helper\"\nversion = \"0.1.0\"\n",
+    )
+    .unwrap();
+    write(local_helper_dir.join("src").join("lib.rs"), "").unwrap();
+
+    let manifest_path = tempdir.path().join("Cargo.toml");
+    let mut manifest = read_to_string(&manifest_path)
+        .unwrap()
+        .parse::<Document>()
+        .unwrap();
+    manifest["dependencies"]["once_cell"] = table();
+    manifest["dependencies"]["once_cell"]["version"] = value("1");
+    manifest["dependencies"]["once_cell"]["default-features"] = value(false);
+    manifest["dependencies"]["local_helper"] = table();
+    manifest["dependencies"]["local_helper"]["path"] = value("local_helper");
+    write(&manifest_path, manifest.to_string()).unwrap();
+
+    std::process::Command::cargo_bin("cargo-dynlint")
+        .unwrap()
+        .args([
+            "dynlint",
+            "upgrade",
+            &tempdir.path().to_string_lossy(),
+            "--breaking",
+        ])
+        .assert()
+        .success();
+
+    let upgraded = read_to_string(&manifest_path)
+        .unwrap()
+        .parse::<Document>()
+        .unwrap();
+
+    // `default-features = false` must survive the upgrade; only `version` should change.
+    assert_eq!(
+        Some(false),
+        upgraded["dependencies"]["once_cell"]["default-features"].as_bool()
+    );
+
+    // The path dependency must stay a path dependency rather than turning into a plain registry
+    // dependency for whatever crate on crates.io happens to share its name.
+    assert_eq!(
+        Some("local_helper"),
+        upgraded["dependencies"]["local_helper"]["path"].as_str()
+    );
+    assert!(upgraded["dependencies"]["local_helper"]
+        .get("version")
+        .is_none());
+}
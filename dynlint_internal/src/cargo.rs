@@ -0,0 +1,88 @@
+//! Helpers for invoking `cargo build`/`cargo test`, with consistent `RUSTFLAGS` handling so that
+//! repeated invocations don't trigger spurious rebuilds.
+
+use crate::env;
+use std::process::Command;
+
+/// Returns a `cargo build` command. `description` is used only for logging.
+pub fn build(description: &str, quiet: bool) -> Command {
+    cargo_command("build", description, quiet)
+}
+
+/// Returns a `cargo test` command. `description` is used only for logging.
+pub fn test(description: &str, quiet: bool) -> Command {
+    cargo_command("test", description, quiet)
+}
+
+fn cargo_command(subcommand: &str, description: &str, quiet: bool) -> Command {
+    let mut command = Command::new("cargo");
+    command.arg(subcommand);
+    if quiet {
+        command.arg("--quiet");
+    }
+    log::debug!("{subcommand}ing {description}");
+    command
+}
+
+/// Sets `RUSTFLAGS` on `command` to `flags`, prepended to whatever the user already had set in
+/// their own environment, instead of clobbering it outright. Overwriting `RUSTFLAGS` silently
+/// drops a user's `-C target-cpu=...` or custom `--cfg`, which is exactly the kind of thing that
+/// only gets noticed when a build starts behaving differently on one machine than another.
+pub fn extend_rustflags(command: &mut Command, flags: &str) {
+    let existing = std::env::var(env::RUSTFLAGS).unwrap_or_default();
+    let combined = if existing.is_empty() {
+        flags.to_owned()
+    } else {
+        format!("{flags} {existing}")
+    };
+    command.env(env::RUSTFLAGS, combined);
+}
+
+/// Clears `RUSTFLAGS` for the duration of a metadata-only probe (e.g. a `cargo metadata` or
+/// `cargo check` run used just to resolve a dependency graph), so that the probe doesn't pick up
+/// flags meant for the real library build and invalidate Cargo's fingerprint for no reason.
+pub fn clear_rustflags_for_metadata(command: &mut Command) {
+    command.env_remove(env::RUSTFLAGS);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Mutex;
+
+    // smoelius: `RUSTFLAGS` is process-wide state; serialize the tests that touch it.
+    static MUTEX: Mutex<()> = Mutex::new(());
+
+    #[test]
+    fn extend_rustflags_prepends_to_existing_value() {
+        let _lock = MUTEX.lock().unwrap();
+
+        std::env::set_var(env::RUSTFLAGS, "-C target-cpu=native");
+        let mut command = Command::new("true");
+        extend_rustflags(&mut command, "--cfg dynlint_lib");
+        std::env::remove_var(env::RUSTFLAGS);
+
+        let rustflags = command
+            .get_envs()
+            .find(|(key, _)| key.to_str() == Some(env::RUSTFLAGS))
+            .and_then(|(_, value)| value)
+            .and_then(|value| value.to_str());
+        assert_eq!(Some("--cfg dynlint_lib -C target-cpu=native"), rustflags);
+    }
+
+    #[test]
+    fn extend_rustflags_with_no_existing_value() {
+        let _lock = MUTEX.lock().unwrap();
+
+        std::env::remove_var(env::RUSTFLAGS);
+        let mut command = Command::new("true");
+        extend_rustflags(&mut command, "--cfg dynlint_lib");
+
+        let rustflags = command
+            .get_envs()
+            .find(|(key, _)| key.to_str() == Some(env::RUSTFLAGS))
+            .and_then(|(_, value)| value)
+            .and_then(|value| value.to_str());
+        assert_eq!(Some("--cfg dynlint_lib"), rustflags);
+    }
+}
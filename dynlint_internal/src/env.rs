@@ -0,0 +1,4 @@
+//! Names of environment variables used by Dynlint's own tooling.
+
+pub const DYNLINT_LIBRARY_PATH: &str = "DYNLINT_LIBRARY_PATH";
+pub const RUSTFLAGS: &str = "RUSTFLAGS";
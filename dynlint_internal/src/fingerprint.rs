@@ -0,0 +1,221 @@
+//! A freshness check for Dynlint libraries, analogous to Cargo's own rebuild-detection.
+//!
+//! Before rebuilding a library, `cargo-dynlint` hashes its source file mtimes, its manifest
+//! (`Cargo.toml`, and `Cargo.lock` if present), and the active toolchain channel and effective
+//! `RUSTFLAGS`, and compares that against the fingerprint recorded the last time the library was
+//! built. If they match, the library is already up to date and the build (and whatever it would
+//! otherwise invalidate downstream) is skipped. This replaces having to pass `--no-build` by hand
+//! after the first build of a session.
+
+use anyhow::{Context, Result};
+use std::{
+    collections::{hash_map::DefaultHasher, HashSet},
+    fs::{create_dir_all, read_to_string, write},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use toml_edit::Document;
+use walkdir::WalkDir;
+
+/// A library's fingerprint: a hash of everything that should force a rebuild if it changes.
+#[derive(PartialEq, Eq)]
+pub struct Fingerprint(u64);
+
+impl Fingerprint {
+    /// Computes the current fingerprint of the library rooted at `library_dir`.
+    pub fn compute(library_dir: &Path, toolchain_channel: &str, rustflags: &str) -> Result<Self> {
+        let mut hasher = DefaultHasher::new();
+        toolchain_channel.hash(&mut hasher);
+        rustflags.hash(&mut hasher);
+
+        let mut visited = HashSet::new();
+        hash_crate_dir(library_dir, &mut hasher, &mut visited)?;
+
+        Ok(Self(hasher.finish()))
+    }
+
+    fn path_for(target_dir: &Path, package_id: &str) -> PathBuf {
+        target_dir
+            .join("dynlint")
+            .join("fingerprints")
+            .join(sanitize(package_id))
+    }
+
+    /// Returns `true` if `self` matches the fingerprint last stored for `package_id`.
+    pub fn is_fresh(&self, target_dir: &Path, package_id: &str) -> bool {
+        let Ok(stored) = read_to_string(Self::path_for(target_dir, package_id)) else {
+            return false;
+        };
+        stored.trim().parse::<u64>().ok() == Some(self.0)
+    }
+
+    /// Records `self` as the fingerprint for `package_id`, so a future `is_fresh` call can skip
+    /// rebuilding it.
+    pub fn store(&self, target_dir: &Path, package_id: &str) -> Result<()> {
+        let path = Self::path_for(target_dir, package_id);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)
+                .with_context(|| format!("`create_dir_all` failed for `{}`", parent.display()))?;
+        }
+        write(&path, self.0.to_string())
+            .with_context(|| format!("`write` failed for `{}`", path.display()))
+    }
+}
+
+/// Folds `crate_dir`'s manifest (by content), lockfile (by content, if present), and `src/` file
+/// mtimes into `hasher`, then recurses into each of its `path` dependencies so that editing a
+/// shared crate like `dynlint_linting` or `dynlint_internal` invalidates every library that
+/// depends on it, not just the library whose own files changed. `visited` guards against
+/// re-hashing (and infinite-looping on) a path dependency shared by more than one crate in the
+/// chain.
+fn hash_crate_dir(
+    crate_dir: &Path,
+    hasher: &mut DefaultHasher,
+    visited: &mut HashSet<PathBuf>,
+) -> Result<()> {
+    let crate_dir = crate_dir
+        .canonicalize()
+        .with_context(|| format!("`canonicalize` failed for `{}`", crate_dir.display()))?;
+    if !visited.insert(crate_dir.clone()) {
+        return Ok(());
+    }
+
+    // The manifest (and lockfile, if any) are hashed by content rather than mtime: bumping a
+    // dependency, adding a crate, or moving the `clippy_utils` pin must invalidate the
+    // fingerprint even if whatever tool made the edit happens to preserve the mtime.
+    let manifest = read_to_string(crate_dir.join("Cargo.toml")).ok();
+    for file_name in ["Cargo.toml", "Cargo.lock"] {
+        if let Ok(contents) = read_to_string(crate_dir.join(file_name)) {
+            contents.hash(hasher);
+        }
+    }
+
+    let mut mtimes = Vec::new();
+    for entry in WalkDir::new(crate_dir.join("src"))
+        .into_iter()
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+    {
+        let mtime = entry
+            .metadata()
+            .with_context(|| format!("could not get metadata for `{}`", entry.path().display()))?
+            .modified()
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        mtimes.push((entry.path().to_path_buf(), mtime));
+    }
+    mtimes.sort();
+    for (path, mtime) in mtimes {
+        path.hash(hasher);
+        mtime.hash(hasher);
+    }
+
+    for path_dependency in path_dependencies(&crate_dir, manifest.as_deref()) {
+        hash_crate_dir(&path_dependency, hasher, visited)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the absolute directories of every `path` dependency declared in `manifest`'s
+/// `[dependencies]`, `[dev-dependencies]`, and `[build-dependencies]` tables.
+fn path_dependencies(crate_dir: &Path, manifest: Option<&str>) -> Vec<PathBuf> {
+    let Some(manifest) = manifest else {
+        return Vec::new();
+    };
+    let Ok(document) = manifest.parse::<Document>() else {
+        return Vec::new();
+    };
+
+    ["dependencies", "dev-dependencies", "build-dependencies"]
+        .into_iter()
+        .filter_map(|key| document[key].as_table_like())
+        .flat_map(|table| {
+            table
+                .iter()
+                .map(|(_, item)| item.clone())
+                .collect::<Vec<_>>()
+        })
+        .filter_map(|item| {
+            item.get("path")
+                .and_then(|item| item.as_str())
+                .map(|relative| crate_dir.join(relative))
+        })
+        .collect()
+}
+
+/// Package ids can contain characters that aren't safe to use verbatim as a file name (e.g. `/`
+/// in a `path+file://` source); replace anything that isn't alphanumeric, `-`, or `_`.
+fn sanitize(package_id: &str) -> String {
+    package_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs::create_dir_all;
+    use tempfile::tempdir;
+
+    fn write_library(dir: &Path, deps: &str, source: &str) {
+        create_dir_all(dir.join("src")).unwrap();
+        write(
+            dir.join("Cargo.toml"),
+            format!("[package]\nname = \"library\"\nversion = \"0.1.0\"\n\n{deps}"),
+        )
+        .unwrap();
+        write(dir.join("src").join("lib.rs"), source).unwrap();
+    }
+
+    #[test]
+    fn is_fresh_after_store() {
+        let tempdir = tempdir().unwrap();
+        write_library(tempdir.path(), "", "");
+
+        let fingerprint = Fingerprint::compute(tempdir.path(), "nightly", "").unwrap();
+        let target_dir = tempdir.path().join("target");
+
+        assert!(!fingerprint.is_fresh(&target_dir, "library 0.1.0"));
+
+        fingerprint.store(&target_dir, "library 0.1.0").unwrap();
+
+        assert!(fingerprint.is_fresh(&target_dir, "library 0.1.0"));
+    }
+
+    #[test]
+    fn editing_a_path_dependency_changes_the_fingerprint() {
+        let tempdir = tempdir().unwrap();
+
+        let helper_dir = tempdir.path().join("helper");
+        write_library(&helper_dir, "", "pub fn helper() {}");
+
+        let library_dir = tempdir.path().join("library");
+        write_library(
+            &library_dir,
+            "[dependencies]\nhelper = { path = \"../helper\" }\n",
+            "",
+        );
+
+        let before = Fingerprint::compute(&library_dir, "nightly", "").unwrap();
+
+        // The manifest is hashed by content rather than mtime, so bumping it here reliably
+        // changes the fingerprint regardless of filesystem mtime resolution.
+        write(
+            helper_dir.join("Cargo.toml"),
+            "[package]\nname = \"helper\"\nversion = \"0.2.0\"\n",
+        )
+        .unwrap();
+
+        let after = Fingerprint::compute(&library_dir, "nightly", "").unwrap();
+
+        assert!(before != after);
+    }
+}
@@ -0,0 +1,8 @@
+//! Internal helpers shared by Dynlint's own binaries and test suites.
+
+pub mod cargo;
+pub mod env;
+pub mod fingerprint;
+pub mod packaging;
+pub mod rustup;
+pub mod testing;
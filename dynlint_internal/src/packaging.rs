@@ -0,0 +1,60 @@
+//! Rewiring freshly generated crates to build against this workspace's local copies of the
+//! Dynlint crates, rather than whatever is published on crates.io.
+
+use anyhow::Context;
+use anyhow::Result;
+use std::{
+    fs::{read_to_string, write},
+    path::{Path, PathBuf},
+};
+use toml_edit::{value, Document, InlineTable};
+
+/// Rewrites `path`'s `Cargo.toml` so every `dynlint*` dependency also carries a `path = "..."`
+/// pointing at this workspace's local copy, so integration tests build against in-tree code
+/// instead of whatever is published on crates.io.
+pub fn use_local_packages(path: &Path) -> Result<()> {
+    let manifest_path = path.join("Cargo.toml");
+    let contents = read_to_string(&manifest_path)
+        .with_context(|| format!("`read_to_string` failed for `{}`", manifest_path.display()))?;
+    let mut document = contents
+        .parse::<Document>()
+        .with_context(|| format!("could not parse `{}`", manifest_path.display()))?;
+
+    let workspace_dir = workspace_dir()?;
+
+    for key in ["dependencies", "dev-dependencies", "build-dependencies"] {
+        let Some(table) = document[key].as_table_mut() else {
+            continue;
+        };
+        let names: Vec<String> = table
+            .iter()
+            .map(|(name, _)| name.to_owned())
+            .filter(|name| name.starts_with("dynlint"))
+            .collect();
+        for name in names {
+            let local_path = workspace_dir.join(&name);
+            if let Some(existing) = table[&name].as_table_like_mut() {
+                existing.insert("path", value(local_path.to_string_lossy().into_owned()));
+                continue;
+            }
+            let version = table[&name].as_str().map(ToOwned::to_owned);
+
+            let mut inline = InlineTable::new();
+            if let Some(version) = version {
+                inline.insert("version", version.into());
+            }
+            inline.insert("path", local_path.to_string_lossy().into_owned().into());
+            table[&name] = value(inline);
+        }
+    }
+
+    write(&manifest_path, document.to_string())
+        .with_context(|| format!("`write` failed for `{}`", manifest_path.display()))
+}
+
+fn workspace_dir() -> Result<PathBuf> {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .map(Path::to_path_buf)
+        .with_context(|| "`dynlint_internal` has no parent directory")
+}
@@ -0,0 +1,33 @@
+//! Helpers for running commands as part of Dynlint's own build/test machinery.
+
+use crate::env;
+use anyhow::{ensure, Context, Result};
+use std::process::Command;
+
+/// Prepares a [`Command`] to be run as a nested Cargo/Dynlint invocation, and checks its result.
+pub trait SanitizeEnvironment {
+    /// Removes environment variables that must not leak from an outer `cargo test`/
+    /// `cargo dynlint` invocation into this nested one (e.g. `DYNLINT_LIBRARY_PATH`, which would
+    /// otherwise cause "found multiple libraries" errors).
+    fn sanitize_environment(&mut self) -> &mut Self;
+
+    /// Runs the command and fails unless it exited successfully.
+    fn success(&mut self) -> Result<()>;
+}
+
+impl SanitizeEnvironment for Command {
+    fn sanitize_environment(&mut self) -> &mut Self {
+        self.env_remove(env::DYNLINT_LIBRARY_PATH)
+            .env_remove("CARGO")
+            .env_remove("RUSTC")
+            .env_remove("RUSTC_WORKSPACE_WRAPPER")
+    }
+
+    fn success(&mut self) -> Result<()> {
+        let status = self
+            .status()
+            .with_context(|| format!("could not get status of `{self:?}`"))?;
+        ensure!(status.success(), "command failed: `{self:?}`");
+        Ok(())
+    }
+}
@@ -0,0 +1,18 @@
+//! Shared setup for Dynlint's own integration tests.
+
+use anyhow::{ensure, Context, Result};
+use assert_cmd::cargo::CommandCargoExt;
+use std::{path::Path, process::Command};
+
+/// Materializes a filled-in `dynlint-template` at `path`, exactly as
+/// `cargo dynlint new <path> --isolate` would, for tests that need a template to already exist
+/// without exercising `new` itself.
+pub fn new_template(path: &Path) -> Result<()> {
+    let status = Command::cargo_bin("cargo-dynlint")
+        .with_context(|| "could not find `cargo-dynlint` binary")?
+        .args(["dynlint", "new", &path.to_string_lossy(), "--isolate"])
+        .status()
+        .with_context(|| "could not get status of `cargo dynlint new`")?;
+    ensure!(status.success(), "`cargo dynlint new` failed");
+    Ok(())
+}
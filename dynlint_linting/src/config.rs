@@ -0,0 +1,27 @@
+//! Support for per-lint configuration via `[package.metadata.dynlint.<lint>]` tables.
+
+use serde::de::DeserializeOwned;
+use std::{env, path::PathBuf};
+
+/// Reads the `[package.metadata.dynlint.<name>]` table for the crate currently being linted, or
+/// returns `T::default()` if no such table (or no metadata at all) is present.
+///
+/// Dynlint drivers run with `CARGO_MANIFEST_DIR` set to the manifest directory of the crate under
+/// lint, exactly as `rustc` itself does when invoked through `cargo`.
+pub fn config_or_default<T: DeserializeOwned + Default>(name: &str) -> T {
+    try_config(name).unwrap_or_default()
+}
+
+fn try_config<T: DeserializeOwned>(name: &str) -> Option<T> {
+    let manifest_dir: PathBuf = env::var_os("CARGO_MANIFEST_DIR")?.into();
+    let contents = std::fs::read_to_string(manifest_dir.join("Cargo.toml")).ok()?;
+    let document: toml::Value = contents.parse().ok()?;
+    document
+        .get("package")?
+        .get("metadata")?
+        .get("dynlint")?
+        .get(name)?
+        .clone()
+        .try_into()
+        .ok()
+}
@@ -0,0 +1,18 @@
+//! Shared infrastructure for writing Dynlint lints.
+
+#![feature(rustc_private)]
+#![warn(unused_extern_crates)]
+
+extern crate rustc_errors;
+extern crate rustc_lint;
+extern crate rustc_span;
+
+mod config;
+mod macros;
+mod suggestion;
+
+#[doc(hidden)]
+pub use paste;
+
+pub use config::config_or_default;
+pub use suggestion::span_lint_and_fix;
@@ -0,0 +1,52 @@
+//! Macros for declaring Dynlint lints.
+//!
+//! Lint authors write the lint's name, default level, and description (plus a rustdoc comment
+//! describing it); these macros declare the underlying `rustc_session` lint, a zero-sized struct
+//! to hold the lint pass, and wire the two together with `impl_lint_pass!`. They also pull in the
+//! handful of `rustc_private` crates every lint pass needs, so individual lint crates don't each
+//! have to repeat `extern crate rustc_lint;` and friends. Callers still write the
+//! `impl EarlyLintPass`/`impl LateLintPass` block themselves.
+
+/// Declares an [`EarlyLintPass`](rustc_lint::EarlyLintPass) lint.
+///
+/// ```ignore
+/// dynlint_linting::declare_early_lint! {
+///     /// ### What it does
+///     /// ...
+///     pub MY_LINT,
+///     Warn,
+///     "description"
+/// }
+/// ```
+#[macro_export]
+macro_rules! declare_early_lint {
+    ($(#[$attr:meta])* pub $name:ident, $level:ident, $desc:literal) => {
+        $crate::declare_lint_pass! { $(#[$attr])* pub $name, $level, $desc }
+    };
+}
+
+/// Declares a [`LateLintPass`](rustc_lint::LateLintPass) lint. See [`declare_early_lint`].
+#[macro_export]
+macro_rules! declare_late_lint {
+    ($(#[$attr:meta])* pub $name:ident, $level:ident, $desc:literal) => {
+        $crate::declare_lint_pass! { $(#[$attr])* pub $name, $level, $desc }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! declare_lint_pass {
+    ($(#[$attr:meta])* pub $name:ident, $level:ident, $desc:literal) => {
+        extern crate rustc_lint;
+        extern crate rustc_session;
+
+        rustc_session::declare_lint! { $(#[$attr])* pub $name, $level, $desc }
+
+        $crate::paste::paste! {
+            #[derive(Default)]
+            pub struct [<$name:camel>];
+
+            rustc_session::impl_lint_pass!([<$name:camel>] => [$name]);
+        }
+    };
+}
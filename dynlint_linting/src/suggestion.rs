@@ -0,0 +1,34 @@
+//! Helpers for lints whose findings can be rewritten automatically.
+
+use clippy_utils::diagnostics::span_lint_and_sugg;
+use rustc_errors::Applicability;
+use rustc_lint::{Lint, LintContext};
+use rustc_span::Span;
+
+/// Emits a lint together with a [`MachineApplicable`](Applicability::MachineApplicable)
+/// suggestion.
+///
+/// This is a thin wrapper around
+/// [`clippy_utils::diagnostics::span_lint_and_sugg`] that pins the applicability, so that lint
+/// authors don't have to import `rustc_errors` themselves just to spell
+/// `Applicability::MachineApplicable`. Suggestions produced this way are picked up by
+/// `cargo dynlint --fix`, which collects them from the driver's `--error-format=json` output and
+/// applies them with the [`rustfix`](https://docs.rs/rustfix) crate.
+pub fn span_lint_and_fix<T: LintContext>(
+    cx: &T,
+    lint: &'static Lint,
+    sp: Span,
+    msg: &str,
+    help: &str,
+    sugg: String,
+) {
+    span_lint_and_sugg(
+        cx,
+        lint,
+        sp,
+        msg,
+        help,
+        sugg,
+        Applicability::MachineApplicable,
+    );
+}
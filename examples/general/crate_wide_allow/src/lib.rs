@@ -4,7 +4,7 @@
 extern crate rustc_ast;
 extern crate rustc_span;
 
-use clippy_utils::diagnostics::span_lint_and_help;
+use dynlint_linting::span_lint_and_fix;
 use if_chain::if_chain;
 use rustc_ast::{AttrStyle, Crate, MetaItem, MetaItemKind};
 use rustc_lint::{EarlyContext, EarlyLintPass};
@@ -27,6 +27,9 @@ dynlint_linting::declare_early_lint! {
     /// ```rust
     /// // Pass `--allow clippy::assertions-on-constants` on the command line.
     /// ```
+    ///
+    /// This lint is machine-applicable: `cargo dynlint --fix` removes the offending attribute
+    /// for you.
     pub CRATE_WIDE_ALLOW,
     Warn,
     "use of `#![allow(...)]` at the crate level"
@@ -52,13 +55,13 @@ impl EarlyLintPass for CrateWideAllow {
                         .collect::<Vec<_>>()
                         .join("::")
                         .replace('_', "-");
-                    span_lint_and_help(
+                    span_lint_and_fix(
                         cx,
                         CRATE_WIDE_ALLOW,
                         attr.span,
                         &format!("silently overrides `--warn {path}` and `--deny {path}`"),
-                        None,
-                        &format!("pass `--allow {path}` on the command line"),
+                        &format!("pass `--allow {path}` on the command line instead"),
+                        String::new(),
                     );
                 }
             }
@@ -0,0 +1,148 @@
+#![feature(rustc_private)]
+#![warn(unused_extern_crates)]
+
+extern crate rustc_hir;
+extern crate rustc_span;
+
+use clippy_utils::{diagnostics::span_lint_and_help, match_def_path};
+use rustc_hir::{Expr, ExprKind};
+use rustc_lint::{LateContext, LateLintPass};
+use serde::Deserialize;
+use std::{ffi::OsStr, sync::OnceLock};
+
+dynlint_linting::declare_late_lint! {
+    /// ### What it does
+    /// Checks for direct calls to `std::env::var`, `std::env::var_os`, `std::env::set_var`, and
+    /// similar functions in library and binary code.
+    ///
+    /// ### Why is this bad?
+    /// Reading or writing the process environment directly scatters a crate's configuration
+    /// surface across every call site, instead of funneling it through a single, testable
+    /// loader. This mirrors Clippy's `disallowed_methods`, but is specialized to the `std::env`
+    /// functions and exempts `build.rs` and test code by default.
+    ///
+    /// ### Example
+    /// ```rust
+    /// let level = std::env::var("LOG_LEVEL").unwrap_or_default();
+    /// ```
+    /// Use instead:
+    /// ```rust
+    /// let level = config::load().log_level;
+    /// ```
+    ///
+    /// ### Configuration
+    /// This lint can be configured with a `[package.metadata.dynlint.disallowed_env_access]`
+    /// table in `Cargo.toml`:
+    /// ```toml
+    /// [package.metadata.dynlint.disallowed_env_access]
+    /// allow = ["std::env::var_os"]
+    /// forbid = ["std::env::vars"]
+    /// ```
+    pub DISALLOWED_ENV_ACCESS,
+    Warn,
+    "direct use of `std::env` accessors outside of `build.rs` and tests"
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+struct Config {
+    allow: Vec<String>,
+    forbid: Vec<String>,
+}
+
+/// The crate's `[package.metadata.dynlint.disallowed_env_access]` table, read from disk at most
+/// once per lint run rather than once per call-expression.
+fn config() -> &'static Config {
+    static CONFIG: OnceLock<Config> = OnceLock::new();
+    CONFIG.get_or_init(|| dynlint_linting::config_or_default("disallowed_env_access"))
+}
+
+static DEFAULT_ENV_FNS: &[&[&str]] = &[
+    &["std", "env", "var"],
+    &["std", "env", "var_os"],
+    &["std", "env", "set_var"],
+    &["std", "env", "remove_var"],
+    &["std", "env", "vars"],
+    &["std", "env", "vars_os"],
+];
+
+impl LateLintPass<'_> for DisallowedEnvAccess {
+    fn check_expr(&mut self, cx: &LateContext<'_>, expr: &Expr<'_>) {
+        if is_exempt(cx, expr) {
+            return;
+        }
+
+        let ExprKind::Call(callee, _) = expr.kind else {
+            return;
+        };
+        let ExprKind::Path(ref qpath) = callee.kind else {
+            return;
+        };
+        let Some(def_id) = cx.qpath_res(qpath, callee.hir_id).opt_def_id() else {
+            return;
+        };
+
+        let config = config();
+
+        for path in DEFAULT_ENV_FNS {
+            let joined = path.join("::");
+            if config.allow.iter().any(|allowed| allowed == &joined) {
+                continue;
+            }
+            if match_def_path(cx, def_id, path) {
+                span_lint_and_help(
+                    cx,
+                    DISALLOWED_ENV_ACCESS,
+                    expr.span,
+                    &format!("use of `{joined}` outside of `build.rs` or test code"),
+                    None,
+                    "route configuration through a central loader instead",
+                );
+            }
+        }
+
+        // A `forbid` entry that merely repeats a default entry (the doc comment's own example
+        // does this) would otherwise flag the same call twice.
+        for path in &config.forbid {
+            if DEFAULT_ENV_FNS
+                .iter()
+                .any(|default| default.join("::") == *path)
+            {
+                continue;
+            }
+            let segments = path.split("::").collect::<Vec<_>>();
+            if match_def_path(cx, def_id, &segments) {
+                span_lint_and_help(
+                    cx,
+                    DISALLOWED_ENV_ACCESS,
+                    expr.span,
+                    &format!("use of `{path}`, which is forbidden by this crate's `Cargo.toml`"),
+                    None,
+                    "route configuration through a central loader instead",
+                );
+            }
+        }
+    }
+}
+
+/// Returns `true` for `build.rs` and for code compiled as part of a test binary, both of which
+/// are exempted from this lint by default.
+fn is_exempt(cx: &LateContext<'_>, expr: &Expr<'_>) -> bool {
+    if cx.tcx.sess.opts.test {
+        return true;
+    }
+    let filename = cx.tcx.sess.source_map().span_to_filename(expr.span);
+    matches!(
+        filename,
+        rustc_span::FileName::Real(real)
+            if real.local_path_if_available().file_name() == Some(OsStr::new("build.rs"))
+    )
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn ui() {
+        dynlint_testing::ui_test_example(env!("CARGO_PKG_NAME"), "ui");
+    }
+}
@@ -0,0 +1,155 @@
+#![feature(rustc_private)]
+#![warn(unused_extern_crates)]
+
+extern crate rustc_ast;
+
+use cargo_metadata::{Metadata, MetadataCommand, Node, Package, PackageId};
+use clippy_utils::diagnostics::span_lint_and_help;
+use rustc_ast::Crate;
+use rustc_lint::{EarlyContext, EarlyLintPass};
+use std::collections::{HashMap, HashSet, VecDeque};
+
+dynlint_linting::declare_early_lint! {
+    /// ### What it does
+    /// Checks whether the crate's dependency graph pulls in more than one, semver-incompatible
+    /// version of the same package.
+    ///
+    /// ### Why is this bad?
+    /// Duplicate versions of a crate bloat the binary and the build, and can cause confusing
+    /// type errors when a type from one version is used where the other is expected. This is
+    /// the same idea as Clippy's `multiple_crate_versions`, offered as a standalone Dynlint
+    /// library so teams can gate it per-workspace independently of Clippy's lint levels.
+    ///
+    /// ### Example
+    /// N/A; this lint inspects `cargo metadata`'s resolve graph rather than the crate's own
+    /// source.
+    pub MULTIPLE_CRATE_VERSIONS,
+    Warn,
+    "multiple versions of the same crate are present in the dependency graph"
+}
+
+impl EarlyLintPass for MultipleCrateVersions {
+    fn check_crate(&mut self, cx: &EarlyContext, krate: &Crate) {
+        let Ok(metadata) = MetadataCommand::new()
+            .other_options(["--filter-platform".to_owned(), current_target()])
+            .exec()
+        else {
+            return;
+        };
+
+        let Some(resolve) = metadata.resolve.as_ref() else {
+            return;
+        };
+
+        let packages_by_id: HashMap<&PackageId, &Package> = metadata
+            .packages
+            .iter()
+            .map(|package| (&package.id, package))
+            .collect();
+
+        let mut ids_by_name: HashMap<&str, Vec<&PackageId>> = HashMap::new();
+        for package in &metadata.packages {
+            ids_by_name
+                .entry(package.name.as_str())
+                .or_default()
+                .push(&package.id);
+        }
+
+        for (name, ids) in &ids_by_name {
+            let versions: HashSet<_> = ids
+                .iter()
+                .filter_map(|id| packages_by_id.get(*id))
+                .map(|package| package.version.clone())
+                .collect();
+            if versions.len() < 2 {
+                continue;
+            }
+            let mut versions: Vec<_> = versions.into_iter().collect();
+            versions.sort();
+            let versions_rendered = versions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            for id in ids {
+                let Some(chain) = dependency_chain(resolve.nodes.as_slice(), &metadata, id) else {
+                    continue;
+                };
+                let chain_rendered = chain
+                    .iter()
+                    .filter_map(|id| packages_by_id.get(id))
+                    .map(|package| format!("{} v{}", package.name, package.version))
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+                span_lint_and_help(
+                    cx,
+                    MULTIPLE_CRATE_VERSIONS,
+                    krate.spans.inner_span,
+                    &format!("multiple versions of `{name}` in the dependency graph: {versions_rendered}"),
+                    None,
+                    &format!("pulled in via: {chain_rendered}"),
+                );
+            }
+        }
+    }
+}
+
+/// Returns the target triple named by the `TARGET` environment variable if it is set, and
+/// otherwise the host triple, e.g. `x86_64-unknown-linux-gnu`, for use with
+/// `cargo metadata --filter-platform`. When `TARGET` is unset and the crate is being
+/// cross-compiled, the graph is therefore filtered for the host, not the compilation target.
+fn current_target() -> String {
+    std::env::var("TARGET").unwrap_or_else(|_| rustc_session::config::host_triple().to_owned())
+}
+
+/// Finds the shortest path from a workspace member to `target` in the resolve graph, inclusive of
+/// both endpoints, so that a lint message can show users exactly which dependency pulled in the
+/// conflicting version. Duplicates caused solely by transitive dependencies still surface here,
+/// because the path necessarily runs through every intermediate crate.
+fn dependency_chain(
+    nodes: &[Node],
+    metadata: &Metadata,
+    target: &PackageId,
+) -> Option<Vec<PackageId>> {
+    let edges: HashMap<&PackageId, &[PackageId]> = nodes
+        .iter()
+        .map(|node| (&node.id, node.dependencies.as_slice()))
+        .collect();
+
+    let mut queue: VecDeque<Vec<PackageId>> = metadata
+        .workspace_members
+        .iter()
+        .map(|id| vec![id.clone()])
+        .collect();
+    let mut visited: HashSet<PackageId> = metadata.workspace_members.iter().cloned().collect();
+
+    while let Some(path) = queue.pop_front() {
+        let last = path.last().expect("path is non-empty");
+        if last == target {
+            return Some(path);
+        }
+        let Some(dependencies) = edges.get(last) else {
+            continue;
+        };
+        for dependency in *dependencies {
+            if visited.insert(dependency.clone()) {
+                let mut next = path.clone();
+                next.push(dependency.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn ui() {
+        // smoelius: The ui test's own dependency graph has no duplicate versions, so this
+        // exercises the lint staying silent on ordinary code rather than firing.
+        dynlint_testing::ui_test_example(env!("CARGO_PKG_NAME"), "ui");
+    }
+}